@@ -0,0 +1,382 @@
+//! A disk-backed map for datasets far larger than RAM, inspired by Solana's
+//! `BucketMap`. Entries are sharded by the top bits of the key's hash across
+//! `max_buckets` files, each memory-mapped and grown independently as it
+//! fills up, so no single bucket ever has to hold (or rehash) the whole map.
+//!
+//! Opt-in via the `mmap` feature, which pulls in the `memmap2` crate.
+
+use std::collections::hash_map::RandomState;
+use std::fs::{self, OpenOptions};
+use std::hash::{BuildHasher, Hash};
+use std::io;
+use std::marker::PhantomData;
+use std::path::PathBuf;
+
+use memmap2::MmapMut;
+
+/// Configuration for a [`BucketMap`].
+pub struct BucketMapConfig {
+    /// Number of on-disk shards; must be a power of two.
+    pub max_buckets: usize,
+    /// Directories to shard bucket files across, round-robin. `None` spills
+    /// to a process-owned temp directory that is erased on `Drop`.
+    pub drives: Option<Vec<PathBuf>>,
+    /// How many slots a bucket will linear-probe before reporting it is full.
+    pub max_search: usize,
+}
+
+impl BucketMapConfig {
+    pub fn new(max_buckets: usize) -> Self {
+        assert!(max_buckets.is_power_of_two(), "max_buckets must be a power of two");
+
+        Self {
+            max_buckets,
+            drives: None,
+            max_search: 8,
+        }
+    }
+}
+
+/// A bucket file reported it can't take the operation as configured; the
+/// caller should grow (and re-map) that one bucket and retry.
+#[derive(Debug, PartialEq, Eq)]
+pub enum BucketMapError {
+    DataNoSpace,
+}
+
+/// A single on-disk record. `#[repr(C)]` gives it a stable layout since it is
+/// read and written directly through a memory-mapped file.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct DiskSlot<K, V> {
+    occupied: bool,
+    hash: u64,
+    key: K,
+    value: V,
+}
+
+/// One memory-mapped shard: a power-of-two array of [`DiskSlot`]s searched
+/// with bounded linear probing.
+struct Bucket<K, V> {
+    path: PathBuf,
+    mmap: MmapMut,
+    capacity: usize,
+    _marker: PhantomData<(K, V)>,
+}
+
+impl<K: Copy + Eq, V: Copy> Bucket<K, V> {
+    fn create(path: PathBuf, capacity: usize) -> io::Result<Self> {
+        let record_size = std::mem::size_of::<DiskSlot<K, V>>();
+
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)?;
+        file.set_len((record_size * capacity) as u64)?;
+
+        let mmap = unsafe { MmapMut::map_mut(&file)? };
+
+        Ok(Self { path, mmap, capacity, _marker: PhantomData })
+    }
+
+    fn slots(&self) -> &[DiskSlot<K, V>] {
+        unsafe { std::slice::from_raw_parts(self.mmap.as_ptr() as *const DiskSlot<K, V>, self.capacity) }
+    }
+
+    fn slots_mut(&mut self) -> &mut [DiskSlot<K, V>] {
+        unsafe { std::slice::from_raw_parts_mut(self.mmap.as_mut_ptr() as *mut DiskSlot<K, V>, self.capacity) }
+    }
+
+    fn ideal_index(&self, hash: u64) -> usize {
+        hash as usize & (self.capacity - 1)
+    }
+
+    fn find(&self, key: K, hash: u64, max_search: usize) -> Option<usize> {
+        let ideal = self.ideal_index(hash);
+
+        for probe in 0..max_search.min(self.capacity) {
+            let idx = (ideal + probe) & (self.capacity - 1);
+            let slot = &self.slots()[idx];
+            if !slot.occupied {
+                return None;
+            }
+            if slot.hash == hash && slot.key == key {
+                return Some(idx);
+            }
+        }
+        None
+    }
+
+    fn insert(&mut self, key: K, hash: u64, value: V, max_search: usize) -> Result<Option<V>, BucketMapError> {
+        let ideal = self.ideal_index(hash);
+        let bound = max_search.min(self.capacity);
+
+        for probe in 0..bound {
+            let idx = (ideal + probe) & (self.capacity - 1);
+            let slot = &mut self.slots_mut()[idx];
+
+            if slot.occupied && slot.hash == hash && slot.key == key {
+                let old = slot.value;
+                slot.value = value;
+                return Ok(Some(old));
+            }
+            if !slot.occupied {
+                *slot = DiskSlot { occupied: true, hash, key, value };
+                return Ok(None);
+            }
+        }
+
+        Err(BucketMapError::DataNoSpace)
+    }
+
+    fn remove(&mut self, key: K, hash: u64, max_search: usize) -> Option<V> {
+        let idx = self.find(key, hash, max_search)?;
+        let old = self.slots()[idx].value;
+        self.backward_shift(idx);
+        Some(old)
+    }
+
+    /// Clears the slot at `idx` and walks the rest of its cluster, moving back
+    /// into the hole every entry whose ideal index does not lie between the
+    /// hole and its current slot, so that a plain clear can't strand a later
+    /// key that shared part of its probe sequence. Unlike `map.rs`'s
+    /// `backward_shift`, this cannot stop at the first entry already at its
+    /// ideal index, since buckets are filled by plain linear probing.
+    fn backward_shift(&mut self, mut hole: usize) {
+        let mask = self.capacity - 1;
+        self.slots_mut()[hole].occupied = false;
+
+        let mut next = (hole + 1) & mask;
+        loop {
+            let slot = self.slots()[next];
+            if !slot.occupied {
+                return;
+            }
+
+            let from_ideal = next.wrapping_sub(self.ideal_index(slot.hash)) & mask;
+            let from_hole = next.wrapping_sub(hole) & mask;
+            if from_ideal >= from_hole {
+                self.slots_mut()[hole] = slot;
+                self.slots_mut()[next].occupied = false;
+                hole = next;
+            }
+            next = (next + 1) & mask;
+        }
+    }
+
+    /// Doubles capacity and re-maps this bucket's file, reinserting every
+    /// occupied slot. Only this one file is touched, not the whole map.
+    ///
+    /// The larger table is built at a temporary path and only `rename`d over
+    /// the live file once it's fully populated, so a failure partway through
+    /// (disk full, permission error) leaves the original bucket file intact
+    /// instead of losing its contents to a half-finished truncate.
+    fn grow(&mut self) -> io::Result<()> {
+        let new_capacity = self.capacity * 2;
+        let occupied: Vec<DiskSlot<K, V>> = self.slots().iter().copied().filter(|slot| slot.occupied).collect();
+
+        let tmp_path = self.path.with_extension("grow.tmp");
+        let mut grown = Bucket::create(tmp_path.clone(), new_capacity)?;
+        for slot in occupied {
+            // The fresh, larger bucket is guaranteed to have room.
+            grown.insert(slot.key, slot.hash, slot.value, new_capacity).ok();
+        }
+
+        fs::rename(&tmp_path, &self.path)?;
+        grown.path = self.path.clone();
+        *self = grown;
+        Ok(())
+    }
+}
+
+/// An upper bound on how many times a single `insert` will grow a bucket
+/// before giving up. Growing only helps when slots are merely dense around
+/// the ideal index; if more keys hash to the same slot than `max_search`
+/// allows, no amount of growth ever widens the window they compete for, so
+/// without a ceiling the retry loop would double the backing file forever.
+/// A handful of doublings is already far more headroom than any real
+/// `max_search` needs to resolve density-driven collisions.
+const MAX_GROW_ATTEMPTS: u32 = 8;
+
+/// An opt-in, disk-backed map for datasets that don't fit in RAM. Values must
+/// be `Copy` since they are written as fixed-size records.
+pub struct BucketMap<K, V> {
+    config: BucketMapConfig,
+    buckets: Vec<Bucket<K, V>>,
+    hash_builder: RandomState,
+    temp_dir: Option<PathBuf>,
+}
+
+impl<K, V> BucketMap<K, V> where K: Copy + Eq + Hash, V: Copy {
+    pub fn new(config: BucketMapConfig) -> io::Result<Self> {
+        assert!(config.max_search > 0, "max_search must be greater than zero");
+
+        let (dirs, temp_dir) = match &config.drives {
+            Some(dirs) => (dirs.clone(), None),
+            None => {
+                let name = RandomState::new().hash_one(std::process::id());
+                let dir = std::env::temp_dir().join(format!("hash_map-bucket_map-{name:x}"));
+                fs::create_dir_all(&dir)?;
+                (vec![dir.clone()], Some(dir))
+            }
+        };
+
+        let initial_capacity = 8;
+        let mut buckets = Vec::with_capacity(config.max_buckets);
+        for i in 0..config.max_buckets {
+            let dir = &dirs[i % dirs.len()];
+            buckets.push(Bucket::create(dir.join(format!("bucket_{i}.dat")), initial_capacity)?);
+        }
+
+        Ok(Self { config, buckets, hash_builder: RandomState::new(), temp_dir })
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> io::Result<Option<V>> {
+        let hash = self.hash_of(&key);
+        let idx = self.bucket_idx(hash);
+
+        for _ in 0..MAX_GROW_ATTEMPTS {
+            match self.buckets[idx].insert(key, hash, value, self.config.max_search) {
+                Ok(old) => return Ok(old),
+                Err(BucketMapError::DataNoSpace) => {
+                    self.buckets[idx].grow()?;
+                }
+            }
+        }
+
+        Err(io::Error::other(
+            "bucket still out of space after repeated growth; too many keys share a probe window",
+        ))
+    }
+
+    pub fn get(&self, key: K) -> Option<V> {
+        let hash = self.hash_of(&key);
+        let bucket = &self.buckets[self.bucket_idx(hash)];
+        bucket.find(key, hash, self.config.max_search).map(|idx| bucket.slots()[idx].value)
+    }
+
+    pub fn remove(&mut self, key: K) -> Option<V> {
+        let hash = self.hash_of(&key);
+        let idx = self.bucket_idx(hash);
+        self.buckets[idx].remove(key, hash, self.config.max_search)
+    }
+
+    fn hash_of(&self, key: &K) -> u64 {
+        self.hash_builder.hash_one(key)
+    }
+
+    /// The top `log2(max_buckets)` bits of the hash select the shard.
+    fn bucket_idx(&self, hash: u64) -> usize {
+        let bits = self.config.max_buckets.trailing_zeros();
+        if bits == 0 { 0 } else { (hash >> (64 - bits)) as usize }
+    }
+}
+
+impl<K, V> Drop for BucketMap<K, V> {
+    fn drop(&mut self) {
+        if let Some(dir) = &self.temp_dir {
+            let _ = fs::remove_dir_all(dir);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let unique = RandomState::new().hash_one((std::process::id(), name));
+        std::env::temp_dir().join(format!("hash_map-bucket_map-test-{unique:x}"))
+    }
+
+    #[test]
+    fn insert_get_remove() {
+        let mut map: BucketMap<u64, u64> = BucketMap::new(BucketMapConfig::new(1)).unwrap();
+
+        assert_eq!(map.insert(1, 10).unwrap(), None);
+        assert_eq!(map.insert(2, 20).unwrap(), None);
+        assert_eq!(map.get(1), Some(10));
+        assert_eq!(map.get(2), Some(20));
+
+        assert_eq!(map.insert(1, 100).unwrap(), Some(10));
+        assert_eq!(map.get(1), Some(100));
+
+        assert_eq!(map.remove(1), Some(100));
+        assert_eq!(map.get(1), None);
+        assert_eq!(map.get(2), Some(20));
+        assert_eq!(map.remove(1), None);
+    }
+
+    #[test]
+    fn grows_past_initial_capacity() {
+        let mut map: BucketMap<u64, u64> = BucketMap::new(BucketMapConfig::new(1)).unwrap();
+
+        for i in 0..64 {
+            assert_eq!(map.insert(i, i * 10).unwrap(), None);
+        }
+        for i in 0..64 {
+            assert_eq!(map.get(i), Some(i * 10));
+        }
+    }
+
+    /// A key whose `Hash` impl ignores its value, so every instance collides
+    /// on the same ideal index no matter how large the bucket grows.
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    struct CollidingKey(u64);
+
+    impl Hash for CollidingKey {
+        fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+            0u64.hash(state);
+        }
+    }
+
+    #[test]
+    fn colliding_keys_within_max_search_succeed() {
+        let mut config = BucketMapConfig::new(1);
+        config.max_search = 4;
+        let mut map: BucketMap<CollidingKey, u64> = BucketMap::new(config).unwrap();
+
+        for i in 0..4 {
+            assert_eq!(map.insert(CollidingKey(i), i * 10).unwrap(), None);
+        }
+        for i in 0..4 {
+            assert_eq!(map.get(CollidingKey(i)), Some(i * 10));
+        }
+    }
+
+    #[test]
+    fn colliding_keys_past_max_search_error_instead_of_hanging() {
+        // Growing a bucket never spreads out keys whose hash is identical,
+        // since their ideal index doesn't change with capacity. Once more of
+        // them collide than `max_search` allows, `insert` must report an
+        // error rather than looping forever doubling the backing file.
+        let mut config = BucketMapConfig::new(1);
+        config.max_search = 4;
+        let mut map: BucketMap<CollidingKey, u64> = BucketMap::new(config).unwrap();
+
+        for i in 0..4 {
+            map.insert(CollidingKey(i), i * 10).unwrap();
+        }
+
+        assert!(map.insert(CollidingKey(4), 40).is_err());
+    }
+
+    #[test]
+    fn removing_a_shared_probe_slot_does_not_strand_the_other_key() {
+        // Two keys that collide on the same ideal index (both hash to 0):
+        // removing the one that landed in that slot must not strand the
+        // other, which probed one slot further.
+        let path = temp_path("shared_probe_slot");
+        let mut bucket = Bucket::<u64, u64>::create(path.clone(), 8).unwrap();
+
+        assert_eq!(bucket.insert(1, 0, 10, 8), Ok(None));
+        assert_eq!(bucket.insert(2, 0, 20, 8), Ok(None));
+
+        assert_eq!(bucket.remove(1, 0, 8), Some(10));
+        assert_eq!(bucket.find(2, 0, 8).map(|idx| bucket.slots()[idx].value), Some(20));
+
+        let _ = fs::remove_file(&path);
+    }
+}
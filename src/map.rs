@@ -0,0 +1,977 @@
+use std::hash::{BuildHasher, Hash};
+use std::collections::hash_map::RandomState;
+use std::mem;
+
+pub struct HashMap<K, V, S = RandomState> {
+    slots: Vec<Option<Slot<K, V>>>,
+    items: usize,
+    hash_builder: S,
+    resize_policy: ResizePolicy,
+}
+
+/// A single occupied or vacant slot in the flat, open-addressed table. The
+/// hash is cached alongside the key/value so probe distance can be recomputed
+/// on lookup/resize without re-hashing the key.
+struct Slot<K, V> {
+    hash: u64,
+    key: K,
+    value: V,
+}
+
+impl<K, V> HashMap<K, V, RandomState> {
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_hasher(capacity, RandomState::new())
+    }
+}
+
+impl<K, V, S: Default> Default for HashMap<K, V, S> {
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<K, V, S> HashMap<K, V, S> {
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self {
+            slots: Vec::new(),
+            items: 0,
+            hash_builder,
+            resize_policy: ResizePolicy::new(),
+        }
+    }
+
+    pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> Self {
+        let resize_policy = ResizePolicy::new();
+        let size = resize_policy.slots_for(capacity);
+
+        Self {
+            slots: (0..size).map(|_| None).collect(),
+            items: 0,
+            hash_builder,
+            resize_policy,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.items
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items == 0
+    }
+
+    /// Iterates over `(&key, &value)` pairs, in no particular order.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter { inner: self.slots.iter() }
+    }
+
+    /// Iterates over `(&key, &mut value)` pairs, in no particular order.
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut { inner: self.slots.iter_mut() }
+    }
+
+    /// Iterates over the keys currently stored, in no particular order.
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys { inner: self.iter() }
+    }
+
+    /// Iterates over the values currently stored, in no particular order.
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values { inner: self.iter() }
+    }
+
+    /// Iterates over mutable references to the values currently stored, in no particular order.
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut { inner: self.iter_mut() }
+    }
+
+    /// Removes every entry, returning an iterator over the removed `(key, value)` pairs.
+    ///
+    /// The slot array itself is kept allocated, so the map can be refilled without a regrow.
+    pub fn drain(&mut self) -> std::vec::IntoIter<(K, V)> {
+        self.items = 0;
+
+        let drained: Vec<(K, V)> = self.slots
+            .iter_mut()
+            .filter_map(|slot| slot.take())
+            .map(|slot| (slot.key, slot.value))
+            .collect();
+
+        drained.into_iter()
+    }
+}
+
+/// The probe distance of the entry that hashed to `ideal_hash` and now sits at
+/// `pos`, i.e. how many slots past its ideal index it has been pushed.
+/// Branchless because `capacity` is always a power of two: the wrapping
+/// subtraction and mask correctly handle the index wrapping around the table.
+fn slot_distance(pos: usize, ideal_hash: u64, mask: usize) -> usize {
+    pos.wrapping_sub(ideal_hash as usize & mask) & mask
+}
+
+/// Places `(hash, key, value)` into `slots` via Robin Hood linear probing,
+/// stealing from any resident whose own probe distance is smaller than the
+/// incoming entry's, and returns the index the original entry ends up at
+/// (which is earlier than where probing ends, if it displaced a richer
+/// resident along the way). `slots` must be non-empty and its length a
+/// power of two. Assumes `key` is not already present.
+fn raw_insert_into<K, V>(slots: &mut [Option<Slot<K, V>>], mut hash: u64, mut key: K, mut value: V) -> usize {
+    let mask = slots.len() - 1;
+    let mut pos = hash as usize & mask;
+    let mut dist = 0usize;
+    let mut planted_at = None;
+
+    loop {
+        match slots[pos].take() {
+            None => {
+                slots[pos] = Some(Slot { hash, key, value });
+                return planted_at.unwrap_or(pos);
+            }
+            Some(resident) => {
+                let resident_dist = slot_distance(pos, resident.hash, mask);
+                if resident_dist < dist {
+                    // steal from the rich: our entry takes this slot, the
+                    // displaced resident keeps probing in its place
+                    slots[pos] = Some(Slot { hash, key, value });
+                    planted_at.get_or_insert(pos);
+
+                    hash = resident.hash;
+                    key = resident.key;
+                    value = resident.value;
+                    dist = resident_dist;
+                } else {
+                    slots[pos] = Some(resident);
+                }
+            }
+        }
+
+        pos = (pos + 1) & mask;
+        dist += 1;
+    }
+}
+
+/// Decides when a [`HashMap`] should grow or shrink its slot array, based on
+/// a load factor expressed as the fraction `load_factor_num / load_factor_den`
+/// of occupied slots.
+struct ResizePolicy {
+    load_factor_num: usize,
+    load_factor_den: usize,
+}
+
+impl ResizePolicy {
+    fn new() -> Self {
+        Self {
+            load_factor_num: 3,
+            load_factor_den: 4,
+        }
+    }
+
+    /// The number of items that `slots` slots can hold before a grow is due.
+    fn capacity_for(&self, slots: usize) -> usize {
+        self.load_factor_num * slots / self.load_factor_den
+    }
+
+    fn should_grow(&self, items: usize, slots: usize) -> bool {
+        slots == 0 || items > self.capacity_for(slots)
+    }
+
+    /// The smallest power-of-two slot count that keeps `items` under the load factor.
+    fn slots_for(&self, items: usize) -> usize {
+        if items == 0 {
+            return 0;
+        }
+
+        let mut slots = 1;
+        while items > self.capacity_for(slots) {
+            slots *= 2;
+        }
+        slots
+    }
+}
+
+impl<K, V, S> HashMap<K, V, S> where K: Hash + Eq, S: BuildHasher {
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        // rehash the HashMap in these cases
+        //   * HashMap has no slots (Just after HashMap::new())
+        //   * HashMap has items, the number of which is over the load factor
+        if self.resize_policy.should_grow(self.items, self.slots.len()) {
+            self.grow();
+        }
+
+        let hash = self.hash_of(&key);
+
+        // if the same key already exists, replace its old value with new value
+        if let Some(idx) = self.find_slot(&key, hash) {
+            let slot = self.slots[idx].as_mut().unwrap();
+            return Some(mem::replace(&mut slot.value, value));
+        }
+
+        self.items += 1;
+        raw_insert_into(&mut self.slots, hash, key, value);
+        None
+    }
+
+    pub fn remove(&mut self, key: K) -> Option<V> {
+        let hash = self.hash_of(&key);
+        let idx = self.find_slot(&key, hash)?;
+
+        self.items -= 1;
+        let removed = self.slots[idx].take().unwrap();
+        self.backward_shift(idx);
+        Some(removed.value)
+    }
+
+    /// Gets the given key's corresponding entry in the map for in-place manipulation.
+    ///
+    /// The key is hashed once. An occupied entry keeps the slot index found by
+    /// that probe, so "insert if absent, else mutate in place" needs no second
+    /// lookup when the key is present; a vacant entry keeps only the hash and
+    /// the key, and inserting into it probes again from the key's ideal slot.
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        if self.resize_policy.should_grow(self.items, self.slots.len()) {
+            self.grow();
+        }
+
+        let hash = self.hash_of(&key);
+
+        match self.find_slot(&key, hash) {
+            Some(index) => Entry::Occupied(OccupiedEntry { slots: &mut self.slots, index }),
+            None => Entry::Vacant(VacantEntry {
+                slots: &mut self.slots,
+                items: &mut self.items,
+                hash,
+                key,
+            }),
+        }
+    }
+
+    pub fn get(&self, key: K) -> Option<&V> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let hash = self.hash_of(&key);
+        self.find_slot(&key, hash).map(|idx| &self.slots[idx].as_ref().unwrap().value)
+    }
+
+    /// Like [`get`](Self::get), but without requiring ownership of `key`. Used
+    /// by [`HashSet`](crate::HashSet), whose membership checks only ever need
+    /// a borrow.
+    pub(crate) fn contains_key(&self, key: &K) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+
+        let hash = self.hash_of(key);
+        self.find_slot(key, hash).is_some()
+    }
+
+    /// Like [`remove`](Self::remove), but without requiring ownership of `key`.
+    pub(crate) fn remove_by_ref(&mut self, key: &K) -> Option<V> {
+        let hash = self.hash_of(key);
+        let idx = self.find_slot(key, hash)?;
+
+        self.items -= 1;
+        let removed = self.slots[idx].take().unwrap();
+        self.backward_shift(idx);
+        Some(removed.value)
+    }
+
+    /// The number of items this map can hold before the next grow, per the
+    /// resize policy's load factor.
+    pub fn capacity(&self) -> usize {
+        self.resize_policy.capacity_for(self.slots.len())
+    }
+
+    /// Reserves capacity for at least `additional` more items, rehashing
+    /// straight to the right size in one pass instead of repeatedly doubling.
+    pub fn reserve(&mut self, additional: usize) {
+        let target = self.resize_policy.slots_for(self.items + additional);
+        if target > self.slots.len() {
+            self.rehash(target);
+        }
+    }
+
+    /// Shrinks the slot array down to the minimum power of two that keeps
+    /// the current items under the load factor.
+    pub fn shrink_to_fit(&mut self) {
+        let target = self.resize_policy.slots_for(self.items);
+        if target < self.slots.len() {
+            self.rehash(target);
+        }
+    }
+
+    fn hash_of(&self, key: &K) -> u64 {
+        self.hash_builder.hash_one(key)
+    }
+
+    /// Walks the probe sequence for `hash`, returning the slot index holding
+    /// `key` if present. Stops early once the running distance exceeds the
+    /// resident slot's own distance, since `key` cannot be further along.
+    fn find_slot(&self, key: &K, hash: u64) -> Option<usize> {
+        if self.slots.is_empty() {
+            return None;
+        }
+
+        let mask = self.slots.len() - 1;
+        let mut pos = hash as usize & mask;
+        let mut dist = 0usize;
+
+        loop {
+            match &self.slots[pos] {
+                None => return None,
+                Some(slot) => {
+                    if slot.hash == hash && slot.key == *key {
+                        return Some(pos);
+                    }
+                    if slot_distance(pos, slot.hash, mask) < dist {
+                        return None;
+                    }
+                }
+            }
+
+            pos = (pos + 1) & mask;
+            dist += 1;
+        }
+    }
+
+    /// Backward-shifts entries following the freshly-vacated `idx` by one slot
+    /// each, for as long as they have a nonzero probe distance, so the probe
+    /// sequence of everything after a removal stays contiguous.
+    fn backward_shift(&mut self, mut idx: usize) {
+        let mask = self.slots.len() - 1;
+
+        loop {
+            let next = (idx + 1) & mask;
+            let at_ideal_slot = match &self.slots[next] {
+                None => true,
+                Some(slot) => slot_distance(next, slot.hash, mask) == 0,
+            };
+            if at_ideal_slot {
+                return;
+            }
+
+            self.slots[idx] = self.slots[next].take();
+            idx = next;
+        }
+    }
+
+    /// Doubles the slot array (or allocates the first slot), the organic
+    /// growth path taken on every `insert`/`entry` once the load factor is exceeded.
+    fn grow(&mut self) {
+        let size = match self.slots.len() {
+            0 => 1,
+            n => n * 2,
+        };
+        self.rehash(size);
+    }
+
+    /// Rehashes all entries into a fresh slot array of exactly `size` slots.
+    fn rehash(&mut self, size: usize) {
+        let old_slots = mem::replace(&mut self.slots, (0..size).map(|_| None).collect());
+
+        for slot in old_slots.into_iter().flatten() {
+            raw_insert_into(&mut self.slots, slot.hash, slot.key, slot.value);
+        }
+    }
+}
+
+/// An iterator over `(&key, &value)` pairs of a [`HashMap`], created by [`HashMap::iter`].
+pub struct Iter<'a, K, V> {
+    inner: std::slice::Iter<'a, Option<Slot<K, V>>>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.by_ref().flatten().next().map(|slot| (&slot.key, &slot.value))
+    }
+}
+
+/// An iterator over `(&key, &mut value)` pairs of a [`HashMap`], created by [`HashMap::iter_mut`].
+pub struct IterMut<'a, K, V> {
+    inner: std::slice::IterMut<'a, Option<Slot<K, V>>>,
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.by_ref().flatten().next().map(|slot| (&slot.key, &mut slot.value))
+    }
+}
+
+/// An iterator over the keys of a [`HashMap`], created by [`HashMap::keys`].
+pub struct Keys<'a, K, V> {
+    inner: Iter<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(key, _)| key)
+    }
+}
+
+/// An iterator over the values of a [`HashMap`], created by [`HashMap::values`].
+pub struct Values<'a, K, V> {
+    inner: Iter<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(_, value)| value)
+    }
+}
+
+/// An iterator over mutable references to the values of a [`HashMap`], created by
+/// [`HashMap::values_mut`].
+pub struct ValuesMut<'a, K, V> {
+    inner: IterMut<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for ValuesMut<'a, K, V> {
+    type Item = &'a mut V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(_, value)| value)
+    }
+}
+
+/// An owning iterator over `(key, value)` pairs of a [`HashMap`], created by its
+/// [`IntoIterator`] implementation.
+pub struct IntoIter<K, V> {
+    inner: std::vec::IntoIter<Option<Slot<K, V>>>,
+}
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.by_ref().flatten().next().map(|slot| (slot.key, slot.value))
+    }
+}
+
+impl<K, V, S> IntoIterator for HashMap<K, V, S> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { inner: self.slots.into_iter() }
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a HashMap<K, V, S> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a mut HashMap<K, V, S> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<K, V, S> FromIterator<(K, V)> for HashMap<K, V, S> where K: Hash + Eq, S: BuildHasher + Default {
+    /// Pre-reserves based on the iterator's size hint so building a map from a
+    /// collection avoids repeatedly doubling as items are inserted.
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let (lower, _) = iter.size_hint();
+
+        let mut map = HashMap::with_capacity_and_hasher(lower, S::default());
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V, S> Extend<(K, V)> for HashMap<K, V, S> where K: Hash + Eq, S: BuildHasher {
+    /// Funnels through `insert`, so duplicate keys keep last-write-wins semantics.
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+/// A view into a single entry in a map, which may either be vacant or occupied.
+///
+/// This enum is constructed from the [`entry`](HashMap::entry) method on [`HashMap`].
+pub enum Entry<'a, K, V> {
+    Occupied(OccupiedEntry<'a, K, V>),
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+impl<'a, K, V> Entry<'a, K, V> {
+    /// Ensures a value is in the entry by inserting the default if empty, and
+    /// returns a mutable reference to the value in the entry.
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the result of the default
+    /// function if empty, and returns a mutable reference to the value in the entry.
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Provides in-place mutable access to an occupied entry before any
+    /// potential inserts into the map.
+    pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Self {
+        match self {
+            Entry::Occupied(mut entry) => {
+                f(entry.get_mut());
+                Entry::Occupied(entry)
+            }
+            Entry::Vacant(entry) => Entry::Vacant(entry),
+        }
+    }
+}
+
+impl<'a, K, V: Default> Entry<'a, K, V> {
+    /// Ensures a value is in the entry by inserting the default value if empty,
+    /// and returns a mutable reference to the value in the entry.
+    pub fn or_default(self) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(V::default()),
+        }
+    }
+}
+
+/// A view into an occupied entry in a [`HashMap`]. It is part of the [`Entry`] enum.
+pub struct OccupiedEntry<'a, K, V> {
+    slots: &'a mut Vec<Option<Slot<K, V>>>,
+    index: usize,
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> {
+    pub fn get(&self) -> &V {
+        &self.slots[self.index].as_ref().unwrap().value
+    }
+
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.slots[self.index].as_mut().unwrap().value
+    }
+
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.slots[self.index].as_mut().unwrap().value
+    }
+}
+
+/// A view into a vacant entry in a [`HashMap`]. It is part of the [`Entry`] enum.
+pub struct VacantEntry<'a, K, V> {
+    slots: &'a mut Vec<Option<Slot<K, V>>>,
+    items: &'a mut usize,
+    hash: u64,
+    key: K,
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V> {
+    pub fn insert(self, value: V) -> &'a mut V {
+        *self.items += 1;
+        let idx = raw_insert_into(self.slots, self.hash, self.key, value);
+        &mut self.slots[idx].as_mut().unwrap().value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert() {
+        // Create new HashMap
+        let mut m = HashMap::new();
+        // No item
+        assert_eq!(m.items, 0);
+        assert_eq!(m.slots.len(), 0);
+        assert_eq!(m.get(100), None);
+        // Insert
+        m.insert(1, 42);
+        assert_eq!(m.items, 1);
+        assert_eq!(m.slots.len(), 1);
+        assert_eq!(*m.get(1).unwrap(), 42);
+        assert_eq!(m.get(100), None);
+        // Insert another value with existing key
+        m.insert(1, 10);
+        assert_eq!(m.items, 1);
+        assert_eq!(m.slots.len(), 2);
+        assert_eq!(*m.get(1).unwrap(), 10);
+        assert_eq!(m.get(100), None);
+        // Insert another value with new key
+        m.insert(2, 20);
+        assert_eq!(m.items, 2);
+        assert_eq!(m.slots.len(), 2);
+        assert_eq!(*m.get(1).unwrap(), 10);
+        assert_eq!(*m.get(2).unwrap(), 20);
+        assert_eq!(m.get(100), None);
+        // Insert another value with new key
+        m.insert(3, 30);
+        assert_eq!(m.items, 3);
+        assert_eq!(m.slots.len(), 4);
+        assert_eq!(*m.get(1).unwrap(), 10);
+        assert_eq!(*m.get(2).unwrap(), 20);
+        assert_eq!(*m.get(3).unwrap(), 30);
+        assert_eq!(m.get(100), None);
+        // Insert another value with new key
+        m.insert(4, 40);
+        assert_eq!(m.items, 4);
+        assert_eq!(m.slots.len(), 4);
+        assert_eq!(*m.get(1).unwrap(), 10);
+        assert_eq!(*m.get(2).unwrap(), 20);
+        assert_eq!(*m.get(3).unwrap(), 30);
+        assert_eq!(*m.get(4).unwrap(), 40);
+        assert_eq!(m.get(100), None);
+        // Insert another value with new key
+        m.insert(5, 50);
+        assert_eq!(m.items, 5);
+        assert_eq!(m.slots.len(), 8);
+        assert_eq!(*m.get(1).unwrap(), 10);
+        assert_eq!(*m.get(2).unwrap(), 20);
+        assert_eq!(*m.get(3).unwrap(), 30);
+        assert_eq!(*m.get(4).unwrap(), 40);
+        assert_eq!(*m.get(5).unwrap(), 50);
+        assert_eq!(m.get(100), None);
+        // Remove value by key
+        m.remove(3);
+        assert_eq!(m.items, 4);
+        assert_eq!(m.slots.len(), 8);
+        assert_eq!(*m.get(1).unwrap(), 10);
+        assert_eq!(*m.get(2).unwrap(), 20);
+        assert_eq!(*m.get(4).unwrap(), 40);
+        assert_eq!(*m.get(5).unwrap(), 50);
+        assert_eq!(m.get(3), None);
+        assert_eq!(m.get(100), None);
+
+        let mut m = HashMap::new();
+        assert_eq!(m.items, 0);
+        assert_eq!(m.slots.len(), 0);
+        assert_eq!(m.get("key100".to_string()), None);
+        m.insert("key".to_string(), 42);
+        assert_eq!(m.items, 1);
+        assert_eq!(m.slots.len(), 1);
+        assert_eq!(*m.get("key".to_string()).unwrap(), 42);
+        assert_eq!(m.get("key100".to_string()), None);
+        m.insert("key".to_string(), 10);
+        assert_eq!(m.items, 1);
+        assert_eq!(m.slots.len(), 2);
+        assert_eq!(*m.get("key".to_string()).unwrap(), 10);
+        assert_eq!(m.get("key100".to_string()), None);
+        m.insert("key2".to_string(), 20);
+        assert_eq!(m.items, 2);
+        assert_eq!(m.slots.len(), 2);
+        assert_eq!(*m.get("key".to_string()).unwrap(), 10);
+        assert_eq!(*m.get("key2".to_string()).unwrap(), 20);
+        assert_eq!(m.get("key100".to_string()), None);
+        m.insert("key3".to_string(), 30);
+        assert_eq!(m.items, 3);
+        assert_eq!(m.slots.len(), 4);
+        assert_eq!(*m.get("key".to_string()).unwrap(), 10);
+        assert_eq!(*m.get("key2".to_string()).unwrap(), 20);
+        assert_eq!(*m.get("key3".to_string()).unwrap(), 30);
+        assert_eq!(m.get("key100".to_string()), None);
+        m.insert("key4".to_string(), 40);
+        assert_eq!(m.items, 4);
+        assert_eq!(m.slots.len(), 4);
+        assert_eq!(*m.get("key".to_string()).unwrap(), 10);
+        assert_eq!(*m.get("key2".to_string()).unwrap(), 20);
+        assert_eq!(*m.get("key3".to_string()).unwrap(), 30);
+        assert_eq!(*m.get("key4".to_string()).unwrap(), 40);
+        assert_eq!(m.get("key100".to_string()), None);
+        m.insert("key5".to_string(), 50);
+        assert_eq!(m.items, 5);
+        assert_eq!(m.slots.len(), 8);
+        assert_eq!(*m.get("key".to_string()).unwrap(), 10);
+        assert_eq!(*m.get("key2".to_string()).unwrap(), 20);
+        assert_eq!(*m.get("key3".to_string()).unwrap(), 30);
+        assert_eq!(*m.get("key4".to_string()).unwrap(), 40);
+        assert_eq!(*m.get("key5".to_string()).unwrap(), 50);
+        assert_eq!(m.get("key100".to_string()), None);
+        m.remove("key3".to_string());
+        assert_eq!(m.items, 4);
+        assert_eq!(m.slots.len(), 8);
+        assert_eq!(*m.get("key".to_string()).unwrap(), 10);
+        assert_eq!(*m.get("key2".to_string()).unwrap(), 20);
+        assert_eq!(*m.get("key4".to_string()).unwrap(), 40);
+        assert_eq!(*m.get("key5".to_string()).unwrap(), 50);
+        assert_eq!(m.get("key3".to_string()), None);
+        assert_eq!(m.get("key100".to_string()), None);
+
+        let mut m = HashMap::new();
+        assert_eq!(m.items, 0);
+        assert_eq!(m.slots.len(), 0);
+        assert_eq!(m.get("key100"), None);
+        m.insert("key", 42);
+        assert_eq!(m.items, 1);
+        assert_eq!(m.slots.len(), 1);
+        assert_eq!(*m.get("key").unwrap(), 42);
+        assert_eq!(m.get("key100"), None);
+        m.insert("key", 10);
+        assert_eq!(m.items, 1);
+        assert_eq!(m.slots.len(), 2);
+        assert_eq!(*m.get("key").unwrap(), 10);
+        assert_eq!(m.get("key100"), None);
+        m.insert("key2", 20);
+        assert_eq!(m.items, 2);
+        assert_eq!(m.slots.len(), 2);
+        assert_eq!(*m.get("key").unwrap(), 10);
+        assert_eq!(*m.get("key2").unwrap(), 20);
+        assert_eq!(m.get("key100"), None);
+        m.insert("key3", 30);
+        assert_eq!(m.items, 3);
+        assert_eq!(m.slots.len(), 4);
+        assert_eq!(*m.get("key").unwrap(), 10);
+        assert_eq!(*m.get("key2").unwrap(), 20);
+        assert_eq!(*m.get("key3").unwrap(), 30);
+        assert_eq!(m.get("key100"), None);
+        m.insert("key4", 40);
+        assert_eq!(m.items, 4);
+        assert_eq!(m.slots.len(), 4);
+        assert_eq!(*m.get("key").unwrap(), 10);
+        assert_eq!(*m.get("key2").unwrap(), 20);
+        assert_eq!(*m.get("key3").unwrap(), 30);
+        assert_eq!(*m.get("key4").unwrap(), 40);
+        assert_eq!(m.get("key100"), None);
+        m.insert("key5", 50);
+        assert_eq!(m.items, 5);
+        assert_eq!(m.slots.len(), 8);
+        assert_eq!(*m.get("key").unwrap(), 10);
+        assert_eq!(*m.get("key2").unwrap(), 20);
+        assert_eq!(*m.get("key3").unwrap(), 30);
+        assert_eq!(*m.get("key4").unwrap(), 40);
+        assert_eq!(*m.get("key5").unwrap(), 50);
+        assert_eq!(m.get("key100"), None);
+        m.remove("key3");
+        assert_eq!(m.items, 4);
+        assert_eq!(m.slots.len(), 8);
+        assert_eq!(*m.get("key").unwrap(), 10);
+        assert_eq!(*m.get("key2").unwrap(), 20);
+        assert_eq!(*m.get("key4").unwrap(), 40);
+        assert_eq!(*m.get("key5").unwrap(), 50);
+        assert_eq!(m.get("key3"), None);
+        assert_eq!(m.get("key100"), None);
+    }
+
+    #[test]
+    fn insert_returns_displaced_value() {
+        let mut m = HashMap::new();
+        assert_eq!(m.insert(1, 42), None);
+        assert_eq!(m.insert(1, 10), Some(42));
+    }
+
+    #[test]
+    fn remove_returns_removed_value() {
+        let mut m = HashMap::new();
+        m.insert(1, 42);
+        assert_eq!(m.remove(1), Some(42));
+        assert_eq!(m.remove(1), None);
+    }
+
+    #[test]
+    fn entry() {
+        let mut m = HashMap::new();
+        // Vacant entry inserts the default
+        *m.entry(1).or_insert(0) += 1;
+        assert_eq!(*m.get(1).unwrap(), 1);
+        // Occupied entry keeps the existing value untouched by or_insert
+        *m.entry(1).or_insert(100) += 1;
+        assert_eq!(*m.get(1).unwrap(), 2);
+        // or_insert_with only calls the closure when the entry is vacant
+        m.entry(2).or_insert_with(|| 5);
+        assert_eq!(*m.get(2).unwrap(), 5);
+        // and_modify only runs for an occupied entry, or_default fills a vacant one
+        m.entry(2).and_modify(|v| *v += 1).or_default();
+        assert_eq!(*m.get(2).unwrap(), 6);
+        m.entry(3).and_modify(|v: &mut i32| *v += 1).or_default();
+        assert_eq!(*m.get(3).unwrap(), 0);
+    }
+
+    #[test]
+    fn with_hasher() {
+        use std::collections::hash_map::RandomState;
+
+        let mut m = HashMap::with_hasher(RandomState::new());
+        m.insert(1, 42);
+        assert_eq!(*m.get(1).unwrap(), 42);
+    }
+
+    #[test]
+    fn with_capacity_and_hasher() {
+        use std::collections::hash_map::RandomState;
+
+        let m: HashMap<i32, i32> = HashMap::with_capacity_and_hasher(10, RandomState::new());
+        assert_eq!(m.slots.len(), 16);
+        assert_eq!(m.items, 0);
+
+        let m: HashMap<i32, i32> = HashMap::with_capacity_and_hasher(0, RandomState::new());
+        assert_eq!(m.slots.len(), 0);
+    }
+
+    #[test]
+    fn with_capacity_and_capacity() {
+        let m: HashMap<i32, i32> = HashMap::with_capacity(10);
+        assert_eq!(m.slots.len(), 16);
+        assert_eq!(m.capacity(), 12);
+
+        let m: HashMap<i32, i32> = HashMap::new();
+        assert_eq!(m.slots.len(), 0);
+        assert_eq!(m.capacity(), 0);
+    }
+
+    #[test]
+    fn reserve() {
+        let mut m: HashMap<i32, i32> = HashMap::new();
+        m.insert(1, 1);
+        m.reserve(20);
+        assert_eq!(m.slots.len(), 32);
+        assert_eq!(*m.get(1).unwrap(), 1);
+
+        // reserve never shrinks
+        m.reserve(0);
+        assert_eq!(m.slots.len(), 32);
+    }
+
+    #[test]
+    fn shrink_to_fit() {
+        let mut m: HashMap<i32, i32> = HashMap::with_capacity(100);
+        assert_eq!(m.slots.len(), 256);
+
+        m.insert(1, 1);
+        m.shrink_to_fit();
+        assert_eq!(m.slots.len(), 2);
+        assert_eq!(*m.get(1).unwrap(), 1);
+
+        m.remove(1);
+        m.shrink_to_fit();
+        assert_eq!(m.slots.len(), 0);
+    }
+
+    #[test]
+    fn robin_hood_displacement_and_removal() {
+        // Fill a 16-slot table up to its 75% load limit, so it never grows and
+        // probes keep running into occupied slots, exercising the
+        // swap-on-insert and backward-shift-on-remove paths rather than just
+        // the happy path.
+        let mut m = HashMap::with_capacity(12);
+        for i in 0..12 {
+            assert_eq!(m.insert(i, i * 10), None);
+        }
+        for i in 0..12 {
+            assert_eq!(*m.get(i).unwrap(), i * 10);
+        }
+
+        // Remove from the middle and re-insert to force backward-shift to
+        // reknit the probe sequence, then confirm every surviving key is
+        // still reachable.
+        for i in (0..12).step_by(2) {
+            assert_eq!(m.remove(i), Some(i * 10));
+        }
+        for i in (0..12).step_by(2) {
+            assert_eq!(m.get(i), None);
+        }
+        for i in (1..12).step_by(2) {
+            assert_eq!(*m.get(i).unwrap(), i * 10);
+        }
+
+        for i in (0..12).step_by(2) {
+            assert_eq!(m.insert(i, i * 100), None);
+        }
+        for i in 0..12 {
+            let expected = if i % 2 == 0 { i * 100 } else { i * 10 };
+            assert_eq!(*m.get(i).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn iter_keys_values() {
+        let mut m = HashMap::new();
+        m.insert(1, 10);
+        m.insert(2, 20);
+        m.insert(3, 30);
+
+        let mut pairs: Vec<_> = m.iter().map(|(k, v)| (*k, *v)).collect();
+        pairs.sort();
+        assert_eq!(pairs, vec![(1, 10), (2, 20), (3, 30)]);
+
+        let mut keys: Vec<_> = m.keys().copied().collect();
+        keys.sort();
+        assert_eq!(keys, vec![1, 2, 3]);
+
+        let mut values: Vec<_> = m.values().copied().collect();
+        values.sort();
+        assert_eq!(values, vec![10, 20, 30]);
+
+        for (_, v) in m.iter_mut() {
+            *v += 1;
+        }
+        let mut values: Vec<_> = m.values_mut().map(|v| *v).collect();
+        values.sort();
+        assert_eq!(values, vec![11, 21, 31]);
+    }
+
+    #[test]
+    fn drain() {
+        let mut m = HashMap::new();
+        m.insert(1, 10);
+        m.insert(2, 20);
+
+        let mut drained: Vec<_> = m.drain().collect();
+        drained.sort();
+        assert_eq!(drained, vec![(1, 10), (2, 20)]);
+        assert_eq!(m.len(), 0);
+        assert_eq!(m.get(1), None);
+
+        m.insert(1, 100);
+        assert_eq!(*m.get(1).unwrap(), 100);
+    }
+
+    #[test]
+    fn into_iterator() {
+        let mut m = HashMap::new();
+        m.insert(1, 10);
+        m.insert(2, 20);
+
+        let mut from_ref: Vec<_> = (&m).into_iter().map(|(k, v)| (*k, *v)).collect();
+        from_ref.sort();
+        assert_eq!(from_ref, vec![(1, 10), (2, 20)]);
+
+        let mut owned: Vec<_> = m.into_iter().collect();
+        owned.sort();
+        assert_eq!(owned, vec![(1, 10), (2, 20)]);
+    }
+
+    #[test]
+    fn from_iterator_and_extend() {
+        let mut m: HashMap<i32, i32> = vec![(1, 10), (2, 20)].into_iter().collect();
+        assert_eq!(m.len(), 2);
+        assert_eq!(*m.get(1).unwrap(), 10);
+
+        // Extend keeps last-write-wins semantics for duplicate keys
+        m.extend(vec![(2, 200), (3, 30)]);
+        assert_eq!(m.len(), 3);
+        assert_eq!(*m.get(2).unwrap(), 200);
+        assert_eq!(*m.get(3).unwrap(), 30);
+    }
+}
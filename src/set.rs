@@ -0,0 +1,143 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+
+use crate::map::HashMap;
+
+/// A hash set implemented as a thin wrapper over [`HashMap`]`<T, ()>`, so it
+/// shares the same slot layout and load-factor/rehash behavior as the map.
+pub struct HashSet<T, S = RandomState> {
+    map: HashMap<T, (), S>,
+}
+
+impl<T> HashSet<T, RandomState> {
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_hasher(capacity, RandomState::new())
+    }
+}
+
+impl<T, S: Default> Default for HashSet<T, S> {
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<T, S> HashSet<T, S> {
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self {
+            map: HashMap::with_hasher(hash_builder),
+        }
+    }
+
+    pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> Self {
+        Self {
+            map: HashMap::with_capacity_and_hasher(capacity, hash_builder),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Iterates over the values currently stored, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.map.keys()
+    }
+}
+
+impl<T, S> HashSet<T, S> where T: Hash + Eq, S: BuildHasher {
+    /// Inserts `value`, returning `true` if it was newly inserted and `false`
+    /// if an equal value was already present (in which case the set is unchanged).
+    pub fn insert(&mut self, value: T) -> bool {
+        self.map.insert(value, ()).is_none()
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.map.contains_key(value)
+    }
+
+    /// Removes `value` from the set, returning `true` if it was present.
+    pub fn remove(&mut self, value: &T) -> bool {
+        self.map.remove_by_ref(value).is_some()
+    }
+}
+
+impl<T, S> HashSet<T, S> where T: Hash + Eq, S: BuildHasher {
+    /// Values in `self` or `other`, without duplicates.
+    pub fn union<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = &'a T> + 'a {
+        self.iter().chain(other.difference(self))
+    }
+
+    /// Values in both `self` and `other`.
+    pub fn intersection<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = &'a T> + 'a {
+        self.iter().filter(move |value| other.contains(value))
+    }
+
+    /// Values in `self` that are not in `other`.
+    pub fn difference<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = &'a T> + 'a {
+        self.iter().filter(move |value| !other.contains(value))
+    }
+
+    /// Values in `self` or `other`, but not both.
+    pub fn symmetric_difference<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = &'a T> + 'a {
+        self.difference(other).chain(other.difference(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_contains_remove() {
+        let mut s = HashSet::new();
+        assert_eq!(s.len(), 0);
+        assert!(s.is_empty());
+
+        assert!(s.insert(1));
+        assert!(!s.insert(1));
+        assert_eq!(s.len(), 1);
+        assert!(s.contains(&1));
+        assert!(!s.contains(&2));
+
+        assert!(s.remove(&1));
+        assert!(!s.remove(&1));
+        assert!(!s.contains(&1));
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    fn set_algebra() {
+        let mut a = HashSet::new();
+        for i in [1, 2, 3] {
+            a.insert(i);
+        }
+        let mut b = HashSet::new();
+        for i in [2, 3, 4] {
+            b.insert(i);
+        }
+
+        let mut union: Vec<_> = a.union(&b).copied().collect();
+        union.sort();
+        assert_eq!(union, vec![1, 2, 3, 4]);
+
+        let mut intersection: Vec<_> = a.intersection(&b).copied().collect();
+        intersection.sort();
+        assert_eq!(intersection, vec![2, 3]);
+
+        let mut difference: Vec<_> = a.difference(&b).copied().collect();
+        difference.sort();
+        assert_eq!(difference, vec![1]);
+
+        let mut symmetric_difference: Vec<_> = a.symmetric_difference(&b).copied().collect();
+        symmetric_difference.sort();
+        assert_eq!(symmetric_difference, vec![1, 4]);
+    }
+}